@@ -1,11 +1,13 @@
 use clap::{Parser, Subcommand};
 use dmdr_core::model::{MetaData, MyModel};
+use std::collections::{HashMap, HashSet};
 use std::io::{BufWriter, Write, stdout};
 use std::sync::Arc;
+use std::time::Instant;
 use std::{fs::File, path::PathBuf};
 
 use dmdr_core::{
-    load_json,
+    load_json, save_json,
     model::{Structure, UuidIndexes},
 };
 
@@ -25,10 +27,16 @@ enum Commands {
         uuid: bool,
         #[clap(short, long)]
         model: Option<String>,
+        #[clap(long, value_parser = parse_depth, default_value = "1")]
+        depth: Depth,
     },
     Write {
         #[clap(short, long)]
         model: Option<String>,
+        #[clap(long, value_parser = parse_depth, default_value = "1")]
+        depth: Depth,
+        #[clap(long, value_parser = parse_diagram_format, default_value = "dot")]
+        format: DiagramFormat,
     },
     Get {
         #[clap(short, long)]
@@ -36,6 +44,127 @@ enum Commands {
         #[clap(long)]
         show_meta: bool,
     },
+    Search {
+        query: String,
+        #[clap(short, long, default_value_t = 5)]
+        limit: usize,
+        #[clap(long)]
+        fields: bool,
+    },
+    Check,
+    Repair {
+        #[clap(short, long)]
+        output: PathBuf,
+    },
+    Diff {
+        other: String,
+    },
+    Bench {
+        workload: PathBuf,
+        #[clap(short, long, default_value_t = 10)]
+        iterations: usize,
+    },
+}
+
+/// Default bounded Levenshtein cutoff `k` used by [`fuzzy_search`] and the
+/// "did you mean" fallback for `Get`/`Enumerate`.
+const DEFAULT_EDIT_DISTANCE_CUTOFF: usize = 2;
+
+struct SearchHit {
+    kind: char,
+    uuid: String,
+    name: String,
+    distance: usize,
+}
+
+/// How many relation hops to follow when extracting a model's subgraph, as
+/// given by `--depth N` or `--depth all`.
+#[derive(Clone)]
+enum Depth {
+    Limited(usize),
+    All,
+}
+
+fn parse_depth(s: &str) -> Result<Depth, String> {
+    if s.eq_ignore_ascii_case("all") {
+        Ok(Depth::All)
+    } else {
+        s.parse::<usize>()
+            .map(Depth::Limited)
+            .map_err(|_| format!("invalid depth: {}", s))
+    }
+}
+
+/// Output backend for `Write`, selected with `--format dot|mermaid|plantuml`.
+#[derive(Clone, Copy)]
+enum DiagramFormat {
+    Dot,
+    Mermaid,
+    PlantUml,
+}
+
+fn parse_diagram_format(s: &str) -> Result<DiagramFormat, String> {
+    match s.to_lowercase().as_str() {
+        "dot" | "graphviz" => Ok(DiagramFormat::Dot),
+        "mermaid" => Ok(DiagramFormat::Mermaid),
+        "plantuml" => Ok(DiagramFormat::PlantUml),
+        _ => Err(format!("unknown diagram format: {}", s)),
+    }
+}
+
+/// A pluggable diagram backend that renders a `Structure` to a textual
+/// diagram format, so `Write` isn't hard-wired to Graphviz DOT.
+trait DiagramRenderer {
+    fn render(&self, data: &Structure, indexes: &UuidIndexes) -> String;
+}
+
+struct DotRenderer;
+
+impl DiagramRenderer for DotRenderer {
+    fn render(&self, data: &Structure, indexes: &UuidIndexes) -> String {
+        dump_er_dot(data, indexes)
+    }
+}
+
+struct MermaidRenderer;
+
+impl DiagramRenderer for MermaidRenderer {
+    fn render(&self, data: &Structure, indexes: &UuidIndexes) -> String {
+        dump_er_mermaid(data, indexes)
+    }
+}
+
+struct PlantUmlRenderer;
+
+impl DiagramRenderer for PlantUmlRenderer {
+    fn render(&self, data: &Structure, indexes: &UuidIndexes) -> String {
+        dump_er_plantuml(data, indexes)
+    }
+}
+
+fn renderer_for(format: DiagramFormat) -> Box<dyn DiagramRenderer> {
+    match format {
+        DiagramFormat::Dot => Box::new(DotRenderer),
+        DiagramFormat::Mermaid => Box::new(MermaidRenderer),
+        DiagramFormat::PlantUml => Box::new(PlantUmlRenderer),
+    }
+}
+
+/// Maps a Django relation kind (the `Debug` form of `rel.relation_type`) to
+/// the crow's-foot cardinality notation shared by Mermaid `erDiagram` and
+/// PlantUML entity relations. `rel.src_field`'s owning model is always the
+/// left-hand side and `rel.target_model` the right-hand side, so the default
+/// (ForeignKey / ManyToOne) case reads "src many, dst one".
+fn relation_cardinality(relation_type_debug: &str) -> &'static str {
+    let lower = relation_type_debug.to_lowercase();
+
+    if lower.contains("onetoone") {
+        "||--||"
+    } else if lower.contains("manytomany") {
+        "}o--o{"
+    } else {
+        "}o--||"
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -44,13 +173,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (data, indexes) = load_json(args.file.into())?;
 
     match args.command {
-        Commands::Enumerate { uuid, model } => {
+        Commands::Enumerate { uuid, model, depth } => {
             let (data, indexes) = if let Some(model) = model {
                 if let Some(model) = get_model_by(&indexes, model.as_str()) {
-                    let uuid = model._meta_data.uuid.clone();
-                    rebuild(data, indexes, uuid)
+                    let model_uuid = model._meta_data.uuid.clone();
+                    reachable_subgraph(&data, &indexes, model_uuid, depth)
                 } else {
-                    panic!("no match {} in models", model);
+                    suggest_and_exit(&data, model.as_str());
                 }
             } else {
                 (data, indexes)
@@ -61,14 +190,88 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             write(&mut out, lines.join("\n").as_bytes());
             println!("");
         }
-        Commands::Write { model } => {
-            write_dot(&data, &indexes, model, Some("data.dot".into()))?;
+        Commands::Write {
+            model,
+            depth,
+            format,
+        } => {
+            let (data, indexes) = if let Some(model) = model {
+                if let Some(target) = get_model_by(&indexes, model.as_str()) {
+                    let model_uuid = target._meta_data.uuid.clone();
+                    reachable_subgraph(&data, &indexes, model_uuid, depth)
+                } else {
+                    suggest_and_exit(&data, model.as_str());
+                }
+            } else {
+                (data, indexes)
+            };
+
+            write_dot(&data, &indexes, format, Some("data.dot".into()))?;
         }
         Commands::Get { model, show_meta } => {
             if let Some(model) = get_model_by(&indexes, model.as_str()) {
                 show_model(&model, show_meta);
             } else {
-                panic!("no match {} in models", model);
+                suggest_and_exit(&data, model.as_str());
+            }
+        }
+        Commands::Search {
+            query,
+            limit,
+            fields,
+        } => {
+            let hits = fuzzy_search(&data, &query, limit, fields, DEFAULT_EDIT_DISTANCE_CUTOFF);
+            for hit in &hits {
+                println!(
+                    "[{}] {}: {} (dist={})",
+                    hit.kind, hit.name, hit.uuid, hit.distance
+                );
+            }
+        }
+        Commands::Check => {
+            let problems = check_structure(&data, &indexes);
+
+            for problem in &problems {
+                eprintln!(
+                    "{}:{}: {}",
+                    problem.source_file, problem.line, problem.description
+                );
+            }
+
+            if !problems.is_empty() {
+                eprintln!("{} problem(s) found", problems.len());
+                std::process::exit(1);
+            }
+        }
+        Commands::Repair { output } => {
+            let repaired = repair_structure(&data, &indexes);
+            save_json(output, &repaired)?;
+        }
+        Commands::Diff { other } => {
+            let (other_data, other_indexes) = load_json(other.into())?;
+
+            let diffs = diff_structures(&data, &indexes, &other_data, &other_indexes);
+
+            for diff in &diffs {
+                let prefix = match diff.kind {
+                    ChangeKind::Added => "+",
+                    ChangeKind::Removed => "-",
+                    ChangeKind::Modified => "~",
+                };
+                println!("{prefix} [{}] {}: {}", diff.entity, diff.name, diff.uuid);
+            }
+
+            let dot = dump_diff_dot(&other_data, &other_indexes, &diffs);
+            let mut out = BufWriter::new(stdout().lock());
+            write(&mut out, dot.as_bytes());
+        }
+        Commands::Bench {
+            workload,
+            iterations,
+        } => {
+            let timings = run_bench(&workload, iterations)?;
+            for timing in &timings {
+                println!("{}", serde_json::to_string(timing)?);
             }
         }
     }
@@ -92,39 +295,705 @@ fn get_model_by(indexes: &UuidIndexes, model_name_or_uuid: &str) -> Option<Arc<M
     }
 }
 
+/// Computes the Levenshtein edit distance between `query` and `candidate`,
+/// bailing out early once it is certain the distance exceeds `k`.
+///
+/// Uses a single-row DP buffer of length `query.len() + 1` instead of the
+/// usual full matrix, and aborts a candidate as soon as every value in the
+/// current row is already greater than `k`.
+fn bounded_levenshtein(query: &str, candidate: &str, k: usize) -> Option<usize> {
+    let query: Vec<char> = query.chars().collect();
+
+    let mut row: Vec<usize> = (0..=query.len()).collect();
+
+    for (j, c_char) in candidate.chars().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = j + 1;
+
+        let mut min_in_row = row[0];
+        for (i, &q_char) in query.iter().enumerate() {
+            let cost = if q_char == c_char { 0 } else { 1 };
+            let deletion = row[i + 1] + 1;
+            let insertion = row[i] + 1;
+            let substitution = prev_diag + cost;
+
+            prev_diag = row[i + 1];
+            row[i + 1] = deletion.min(insertion).min(substitution);
+
+            min_in_row = min_in_row.min(row[i + 1]);
+        }
+
+        if min_in_row > k {
+            return None;
+        }
+    }
+
+    let distance = row[query.len()];
+    if distance <= k { Some(distance) } else { None }
+}
+
+/// Edit distance between `query` and `candidate`, treating a case-insensitive
+/// prefix match as distance 0 before falling back to [`bounded_levenshtein`].
+fn match_distance(query: &str, candidate: &str, k: usize) -> Option<usize> {
+    if candidate.to_lowercase().starts_with(&query.to_lowercase()) {
+        return Some(0);
+    }
+
+    bounded_levenshtein(query, candidate, k)
+}
+
+/// Fuzzy-matches `query` against every model's `object_name`/`model_name`
+/// (and, if `include_fields` is set, every `field.name`) across `data`,
+/// returning the top `limit` hits ranked by (edit distance, name length, name).
+fn fuzzy_search(
+    data: &Structure,
+    query: &str,
+    limit: usize,
+    include_fields: bool,
+    k: usize,
+) -> Vec<SearchHit> {
+    let mut hits = Vec::new();
+
+    for model in &data.models {
+        for name in [&model.object_name, &model.model_name] {
+            if let Some(distance) = match_distance(query, name, k) {
+                hits.push(SearchHit {
+                    kind: 'M',
+                    uuid: model._meta_data.uuid.clone(),
+                    name: name.to_string(),
+                    distance,
+                });
+            }
+        }
+
+        if include_fields {
+            for field in &model.fields {
+                if let Some(distance) = match_distance(query, &field.name, k) {
+                    hits.push(SearchHit {
+                        kind: 'F',
+                        uuid: field._meta_data.uuid.clone(),
+                        name: field.name.clone(),
+                        distance,
+                    });
+                }
+            }
+        }
+    }
+
+    // A model can match on both `object_name` and `model_name`; keep only its
+    // closer hit per (kind, uuid) instead of relying on sort-adjacency.
+    let mut best_by_key: HashMap<(char, String), SearchHit> = HashMap::new();
+    for hit in hits {
+        let key = (hit.kind, hit.uuid.clone());
+        match best_by_key.get(&key) {
+            Some(existing) if existing.distance <= hit.distance => {}
+            _ => {
+                best_by_key.insert(key, hit);
+            }
+        }
+    }
+    let mut hits: Vec<SearchHit> = best_by_key.into_values().collect();
+
+    hits.sort_by(|a, b| {
+        a.distance
+            .cmp(&b.distance)
+            .then(a.name.len().cmp(&b.name.len()))
+            .then(a.name.cmp(&b.name))
+    });
+    hits.truncate(limit);
+
+    hits
+}
+
+/// Prints "did you mean" suggestions for `query` and exits cleanly, so a
+/// slightly-off `--model` value points the user at the exact match to rerun
+/// `Get`/`Enumerate` with instead of dumping a panic backtrace.
+fn suggest_and_exit(data: &Structure, query: &str) -> ! {
+    let hits = fuzzy_search(data, query, 5, true, DEFAULT_EDIT_DISTANCE_CUTOFF);
+
+    if hits.is_empty() {
+        eprintln!("no match {} in models", query);
+        std::process::exit(1);
+    }
+
+    eprintln!("no match {} in models, did you mean:", query);
+    for hit in &hits {
+        eprintln!("  [{}] {}: {}", hit.kind, hit.name, hit.uuid);
+    }
+
+    std::process::exit(1);
+}
+
+/// A single referential-integrity problem found by [`check_structure`],
+/// keyed by the offending entity's `MetaData.code.source_file`/`line_number`
+/// so it can be pointed back at the Django source that produced it.
+struct Problem {
+    description: String,
+    source_file: String,
+    line: String,
+}
+
+/// Validates `data`/`indexes` for referential integrity: relations pointing
+/// at a missing `target_model`, relations whose `src_field` doesn't resolve
+/// through `indexes`, duplicate uuids across models and across fields, and
+/// models that participate in no relation.
+fn check_structure(data: &Structure, indexes: &UuidIndexes) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let model_uuids: HashSet<String> = data
+        .models
+        .iter()
+        .map(|model| model._meta_data.uuid.clone())
+        .collect();
+
+    let mut seen_model_uuids: HashSet<String> = HashSet::new();
+    for model in &data.models {
+        if !seen_model_uuids.insert(model._meta_data.uuid.clone()) {
+            problems.push(Problem {
+                description: format!(
+                    "duplicate model uuid {} ({})",
+                    model._meta_data.uuid, model.object_name
+                ),
+                source_file: model._meta_data.code.source_file.clone(),
+                line: model._meta_data.code.line_number.to_string(),
+            });
+        }
+    }
+
+    let mut seen_field_uuids: HashSet<String> = HashSet::new();
+    for model in &data.models {
+        for field in &model.fields {
+            if !seen_field_uuids.insert(field._meta_data.uuid.clone()) {
+                problems.push(Problem {
+                    description: format!(
+                        "duplicate field uuid {} ({}.{})",
+                        field._meta_data.uuid, model.object_name, field.name
+                    ),
+                    source_file: field._meta_data.code.source_file.clone(),
+                    line: field._meta_data.code.line_number.to_string(),
+                });
+            }
+        }
+    }
+
+    let mut related_model_uuids: HashSet<String> = HashSet::new();
+    for rel in &data.relations {
+        if !indexes.has_field(&rel.src_field) {
+            problems.push(Problem {
+                description: format!(
+                    "relation src_field {} does not resolve to a known model",
+                    rel.src_field
+                ),
+                source_file: rel._meta_data.code.source_file.clone(),
+                line: rel._meta_data.code.line_number.to_string(),
+            });
+        } else {
+            related_model_uuids.insert(indexes.get_model_from_field(&rel.src_field).to_string());
+        }
+
+        if !model_uuids.contains(&rel.target_model) {
+            problems.push(Problem {
+                description: format!(
+                    "relation target_model {} is absent from data.models",
+                    rel.target_model
+                ),
+                source_file: rel._meta_data.code.source_file.clone(),
+                line: rel._meta_data.code.line_number.to_string(),
+            });
+        } else {
+            related_model_uuids.insert(rel.target_model.clone());
+        }
+    }
+
+    for model in &data.models {
+        if !related_model_uuids.contains(&model._meta_data.uuid) {
+            problems.push(Problem {
+                description: format!(
+                    "model {} ({}) participates in no relation",
+                    model.object_name, model._meta_data.uuid
+                ),
+                source_file: model._meta_data.code.source_file.clone(),
+                line: model._meta_data.code.line_number.to_string(),
+            });
+        }
+    }
+
+    problems
+}
+
+/// Produces a cleaned copy of `data`: dangling relations (unresolvable
+/// `src_field` or missing `target_model`) are dropped, and models/fields
+/// sharing a duplicate uuid are reduced to their first occurrence.
+fn repair_structure(data: &Structure, indexes: &UuidIndexes) -> Structure {
+    let model_uuids: HashSet<String> = data
+        .models
+        .iter()
+        .map(|model| model._meta_data.uuid.clone())
+        .collect();
+
+    let mut seen_model_uuids: HashSet<String> = HashSet::new();
+    let mut seen_field_uuids: HashSet<String> = HashSet::new();
+    let mut new_models = Vec::new();
+
+    for model in &data.models {
+        if !seen_model_uuids.insert(model._meta_data.uuid.clone()) {
+            continue;
+        }
+
+        let mut model = model.clone();
+        model
+            .fields
+            .retain(|field| seen_field_uuids.insert(field._meta_data.uuid.clone()));
+        new_models.push(model);
+    }
+
+    let new_relations = data
+        .relations
+        .iter()
+        .filter(|rel| {
+            indexes.has_field(&rel.src_field) && model_uuids.contains(&rel.target_model)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    Structure {
+        models: new_models,
+        relations: new_relations,
+    }
+}
+
+/// Which side of a [`diff_structures`] comparison an entity fell on.
+enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// One model- or field-level change between two `Structure` snapshots, as
+/// produced by [`diff_structures`]. `entity` is `'M'`/`'F'`, matching the
+/// `[M]`/`[F]` convention used by `enumerate`/`fuzzy_search`.
+struct EntityDiff {
+    kind: ChangeKind,
+    entity: char,
+    uuid: String,
+    name: String,
+}
+
+/// A comparable fingerprint for a model: its `model_name`/`db_table`/
+/// `app_label`, sorted field names, and sorted relation signatures (so a
+/// model is "modified" if any of those changed between snapshots).
+fn model_signature(data: &Structure, indexes: &UuidIndexes, model: &MyModel) -> String {
+    let mut field_names: Vec<&str> = model.fields.iter().map(|field| field.name.as_str()).collect();
+    field_names.sort();
+
+    let mut rel_signatures: Vec<String> = data
+        .relations
+        .iter()
+        .filter_map(|rel| {
+            let src_model_uuid = indexes.get_model_from_field(&rel.src_field).to_string();
+            if src_model_uuid == model._meta_data.uuid {
+                Some(format!(
+                    "out:{}:{:?}",
+                    rel.target_model, rel.relation_type
+                ))
+            } else if rel.target_model == model._meta_data.uuid {
+                Some(format!("in:{}:{:?}", src_model_uuid, rel.relation_type))
+            } else {
+                None
+            }
+        })
+        .collect();
+    rel_signatures.sort();
+
+    format!(
+        "{}|{}|{}|{}|{}",
+        model.model_name,
+        model.db_table,
+        model.app_label,
+        field_names.join(","),
+        rel_signatures.join(",")
+    )
+}
+
+/// Matches a model's fields between `left_model` and `right_model` by uuid,
+/// falling back to `name` when a field's uuid changed between snapshots, and
+/// reports added/removed/renamed entries.
+fn diff_fields(left_model: &MyModel, right_model: &MyModel) -> Vec<EntityDiff> {
+    let mut right_by_uuid: HashMap<&str, _> = HashMap::new();
+    let mut right_by_name: HashMap<&str, _> = HashMap::new();
+    for field in &right_model.fields {
+        right_by_uuid.insert(field._meta_data.uuid.as_str(), field);
+        right_by_name.insert(field.name.as_str(), field);
+    }
+
+    let mut matched_right_uuids: HashSet<String> = HashSet::new();
+    let mut diffs = Vec::new();
+
+    for left_field in &left_model.fields {
+        let matched = right_by_uuid
+            .get(left_field._meta_data.uuid.as_str())
+            .or_else(|| right_by_name.get(left_field.name.as_str()));
+
+        match matched {
+            Some(right_field) => {
+                matched_right_uuids.insert(right_field._meta_data.uuid.clone());
+
+                if left_field.name != right_field.name {
+                    diffs.push(EntityDiff {
+                        kind: ChangeKind::Modified,
+                        entity: 'F',
+                        uuid: right_field._meta_data.uuid.clone(),
+                        name: right_field.name.clone(),
+                    });
+                }
+            }
+            None => {
+                diffs.push(EntityDiff {
+                    kind: ChangeKind::Removed,
+                    entity: 'F',
+                    uuid: left_field._meta_data.uuid.clone(),
+                    name: left_field.name.clone(),
+                });
+            }
+        }
+    }
+
+    for right_field in &right_model.fields {
+        if !matched_right_uuids.contains(&right_field._meta_data.uuid) {
+            diffs.push(EntityDiff {
+                kind: ChangeKind::Added,
+                entity: 'F',
+                uuid: right_field._meta_data.uuid.clone(),
+                name: right_field.name.clone(),
+            });
+        }
+    }
+
+    diffs
+}
+
+/// Matches models between `left` and `right` by uuid, falling back to
+/// `object_name` when a model's uuid changed between snapshots, and reports
+/// added/removed/modified entries (plus every added/removed/renamed field
+/// nested inside a matched model, via [`diff_fields`]). "Modified" covers
+/// any change to `model_name`, `db_table`, `app_label`, the field set, or
+/// relations.
+fn diff_structures(
+    left: &Structure,
+    left_indexes: &UuidIndexes,
+    right: &Structure,
+    right_indexes: &UuidIndexes,
+) -> Vec<EntityDiff> {
+    let mut right_by_uuid: HashMap<&str, &MyModel> = HashMap::new();
+    let mut right_by_name: HashMap<&str, &MyModel> = HashMap::new();
+    for model in &right.models {
+        right_by_uuid.insert(model._meta_data.uuid.as_str(), model);
+        right_by_name.insert(model.object_name.as_str(), model);
+    }
+
+    let mut matched_right_uuids: HashSet<String> = HashSet::new();
+    let mut diffs = Vec::new();
+
+    for left_model in &left.models {
+        let matched = right_by_uuid
+            .get(left_model._meta_data.uuid.as_str())
+            .or_else(|| right_by_name.get(left_model.object_name.as_str()));
+
+        match matched {
+            Some(right_model) => {
+                matched_right_uuids.insert(right_model._meta_data.uuid.clone());
+
+                let left_sig = model_signature(left, left_indexes, left_model);
+                let right_sig = model_signature(right, right_indexes, right_model);
+
+                if left_sig != right_sig {
+                    diffs.push(EntityDiff {
+                        kind: ChangeKind::Modified,
+                        entity: 'M',
+                        uuid: right_model._meta_data.uuid.clone(),
+                        name: right_model.object_name.clone(),
+                    });
+                }
+
+                diffs.extend(diff_fields(left_model, right_model));
+            }
+            None => {
+                diffs.push(EntityDiff {
+                    kind: ChangeKind::Removed,
+                    entity: 'M',
+                    uuid: left_model._meta_data.uuid.clone(),
+                    name: left_model.object_name.clone(),
+                });
+            }
+        }
+    }
+
+    for right_model in &right.models {
+        if !matched_right_uuids.contains(&right_model._meta_data.uuid) {
+            diffs.push(EntityDiff {
+                kind: ChangeKind::Added,
+                entity: 'M',
+                uuid: right_model._meta_data.uuid.clone(),
+                name: right_model.object_name.clone(),
+            });
+        }
+    }
+
+    diffs
+}
+
+/// Renders `right` as a DOT graph annotated with `diffs`: added nodes green,
+/// removed nodes (pulled from `diffs` since they no longer exist in `right`)
+/// red, modified nodes yellow, and every relation edge in `right` (so the
+/// diagram still shows how the schema's models relate, not just which
+/// changed).
+fn dump_diff_dot(right: &Structure, right_indexes: &UuidIndexes, diffs: &[EntityDiff]) -> String {
+    let mut dot = String::from("digraph ERDiff {\n");
+
+    let model_diff_by_uuid: HashMap<&str, &EntityDiff> = diffs
+        .iter()
+        .filter(|diff| diff.entity == 'M')
+        .map(|diff| (diff.uuid.as_str(), diff))
+        .collect();
+
+    for model in &right.models {
+        let color = match model_diff_by_uuid.get(model._meta_data.uuid.as_str()) {
+            Some(EntityDiff {
+                kind: ChangeKind::Added,
+                ..
+            }) => "green",
+            Some(EntityDiff {
+                kind: ChangeKind::Modified,
+                ..
+            }) => "yellow",
+            _ => "white",
+        };
+
+        dot.push_str(&format!(
+            "  \"{uuid}\" [label=\"{label}\", style=filled, fillcolor={color}];\n",
+            uuid = model._meta_data.uuid,
+            label = model.object_name,
+        ));
+    }
+
+    for diff in diffs {
+        if diff.entity == 'M' && matches!(diff.kind, ChangeKind::Removed) {
+            dot.push_str(&format!(
+                "  \"{uuid}\" [label=\"{label}\", style=filled, fillcolor=red];\n",
+                uuid = diff.uuid,
+                label = diff.name,
+            ));
+        }
+    }
+
+    for rel in &right.relations {
+        let src_model_uuid = right_indexes.get_model_from_field(&rel.src_field);
+        let dst_model_uuid = &rel.target_model;
+        let rel_label = format!("{:?}", rel.relation_type);
+
+        dot.push_str(&format!(
+            "  \"{src}\" -> \"{dst}\" [label=\"{lbl}\"];\n",
+            src = src_model_uuid,
+            dst = dst_model_uuid,
+            lbl = rel_label
+        ));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// A named JSON workload for [`run_bench`]: which input files to load and
+/// which stages to exercise against each. `stages` defaults to the full set
+/// so a minimal manifest just needs a file list.
+#[derive(serde::Deserialize)]
+struct BenchWorkload {
+    files: Vec<String>,
+    #[serde(default = "default_bench_stages")]
+    stages: Vec<String>,
+}
+
+fn default_bench_stages() -> Vec<String> {
+    vec![
+        "load_json".to_string(),
+        "index".to_string(),
+        "enumerate".to_string(),
+        "dump_er_dot".to_string(),
+    ]
+}
+
+/// Min/median/max wall-clock timing for one stage against one workload
+/// file, plus the model/field/relation counts it was measured against.
+#[derive(serde::Serialize)]
+struct StageTiming {
+    stage: String,
+    file: String,
+    min_ms: f64,
+    median_ms: f64,
+    max_ms: f64,
+    model_count: usize,
+    field_count: usize,
+    relation_count: usize,
+}
+
+fn summarize_samples(
+    stage: &str,
+    file: &str,
+    samples: &mut [f64],
+    model_count: usize,
+    field_count: usize,
+    relation_count: usize,
+) -> StageTiming {
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    StageTiming {
+        stage: stage.to_string(),
+        file: file.to_string(),
+        min_ms: samples[0],
+        median_ms: samples[samples.len() / 2],
+        max_ms: samples[samples.len() - 1],
+        model_count,
+        field_count,
+        relation_count,
+    }
+}
+
+/// Runs `load_json`, `UuidIndexes::new`, `enumerate`, and `dump_er_dot`
+/// `iterations` times against every file in `workload`'s manifest, and
+/// reports per-stage min/median/max timings alongside entity counts.
+/// `load_json` and `UuidIndexes::new` run on every iteration; `enumerate`
+/// and `dump_er_dot` run, and a stage is reported, only when the manifest's
+/// `stages` lists it.
+fn run_bench(
+    workload_path: &PathBuf,
+    iterations: usize,
+) -> Result<Vec<StageTiming>, Box<dyn std::error::Error>> {
+    if iterations == 0 {
+        return Err("--iterations must be at least 1".into());
+    }
+
+    let manifest = std::fs::read_to_string(workload_path)?;
+    let workload: BenchWorkload = serde_json::from_str(&manifest)?;
+
+    let mut timings = Vec::new();
+
+    for file in &workload.files {
+        let mut load_samples = Vec::with_capacity(iterations);
+        let mut index_samples = Vec::with_capacity(iterations);
+        let mut enumerate_samples = Vec::with_capacity(iterations);
+        let mut dot_samples = Vec::with_capacity(iterations);
+
+        let mut model_count = 0;
+        let mut field_count = 0;
+        let mut relation_count = 0;
+
+        for _ in 0..iterations {
+            // load_json/index always run: every later stage needs `data`/`indexes`
+            // to operate on, even when the manifest doesn't ask to time them.
+            let load_start = Instant::now();
+            let (data, indexes) = load_json(file.into())?;
+            if workload.stages.iter().any(|stage| stage == "load_json") {
+                load_samples.push(load_start.elapsed().as_secs_f64() * 1000.0);
+            }
+
+            let index_start = Instant::now();
+            let _ = UuidIndexes::new(&data);
+            if workload.stages.iter().any(|stage| stage == "index") {
+                index_samples.push(index_start.elapsed().as_secs_f64() * 1000.0);
+            }
+
+            if workload.stages.iter().any(|stage| stage == "enumerate") {
+                let enumerate_start = Instant::now();
+                let _ = enumerate(&data, &indexes, false);
+                enumerate_samples.push(enumerate_start.elapsed().as_secs_f64() * 1000.0);
+            }
+
+            if workload.stages.iter().any(|stage| stage == "dump_er_dot") {
+                let dot_start = Instant::now();
+                let _ = dump_er_dot(&data, &indexes);
+                dot_samples.push(dot_start.elapsed().as_secs_f64() * 1000.0);
+            }
+
+            model_count = data.models.len();
+            field_count = data.models.iter().map(|model| model.fields.len()).sum();
+            relation_count = data.relations.len();
+        }
+
+        if !load_samples.is_empty() {
+            timings.push(summarize_samples(
+                "load_json",
+                file,
+                &mut load_samples,
+                model_count,
+                field_count,
+                relation_count,
+            ));
+        }
+
+        if !index_samples.is_empty() {
+            timings.push(summarize_samples(
+                "index",
+                file,
+                &mut index_samples,
+                model_count,
+                field_count,
+                relation_count,
+            ));
+        }
+
+        if !enumerate_samples.is_empty() {
+            timings.push(summarize_samples(
+                "enumerate",
+                file,
+                &mut enumerate_samples,
+                model_count,
+                field_count,
+                relation_count,
+            ));
+        }
+
+        if !dot_samples.is_empty() {
+            timings.push(summarize_samples(
+                "dump_er_dot",
+                file,
+                &mut dot_samples,
+                model_count,
+                field_count,
+                relation_count,
+            ));
+        }
+    }
+
+    Ok(timings)
+}
+
 fn write_dot(
     data: &Structure,
     indexes: &UuidIndexes,
-    target_model: Option<String>,
+    format: DiagramFormat,
     output_path: Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let dot = dump_er_dot(data, indexes, target_model);
+    let diagram = renderer_for(format).render(data, indexes);
 
     if let Some(path) = output_path {
         let file = File::create(path)?;
         let mut out = BufWriter::new(file);
-        write(&mut out, dot.as_bytes());
+        write(&mut out, diagram.as_bytes());
     } else {
         let file = stdout();
         let mut out = BufWriter::new(file.lock());
-        write(&mut out, dot.as_bytes());
+        write(&mut out, diagram.as_bytes());
     };
 
     Ok(())
 }
 
-fn dump_er_dot(data: &Structure, indexes: &UuidIndexes, target_model: Option<String>) -> String {
+fn dump_er_dot(data: &Structure, indexes: &UuidIndexes) -> String {
     let mut dot = String::from("digraph ER {\n");
 
     // define node
     for model in &data.models {
         let uuid = &model._meta_data.uuid;
-        if let Some(target) = target_model.as_ref()
-            && target != uuid
-        {
-            continue;
-        }
-
         let label = &model.object_name;
         dot.push_str(&format!("  \"{uuid}\" [label=\"{label}\"];\n"));
     }
@@ -134,12 +1003,6 @@ fn dump_er_dot(data: &Structure, indexes: &UuidIndexes, target_model: Option<Str
         let src_model_uuid = indexes.get_model_from_field(&rel.src_field);
         let dst_model_uuid = &rel.target_model;
 
-        // if let Some(target) = target_model.as_ref()
-        //     && (target != src_model_uuid || target != dst_model_uuid)
-        // {
-        //     continue;
-        // }
-
         let rel_label = format!("{:?}", rel.relation_type);
 
         dot.push_str(&format!(
@@ -154,6 +1017,74 @@ fn dump_er_dot(data: &Structure, indexes: &UuidIndexes, target_model: Option<Str
     dot
 }
 
+/// Resolves a model uuid to its `object_name` for diagram labels, falling
+/// back to the raw uuid when it's dangling (points at no model in `indexes`)
+/// instead of panicking like `UuidIndexes::get_model` would.
+fn model_label(indexes: &UuidIndexes, uuid: &str) -> String {
+    if indexes.has_model(uuid) {
+        indexes.get_model(uuid).object_name.clone()
+    } else {
+        uuid.to_string()
+    }
+}
+
+fn dump_er_mermaid(data: &Structure, indexes: &UuidIndexes) -> String {
+    let mut mermaid = String::from("erDiagram\n");
+
+    for model in &data.models {
+        mermaid.push_str(&format!("  {} {{\n", model.object_name));
+        for field in &model.fields {
+            mermaid.push_str(&format!("    string {}\n", field.name));
+        }
+        mermaid.push_str("  }\n");
+    }
+
+    for rel in &data.relations {
+        let src_model_uuid = indexes.get_model_from_field(&rel.src_field);
+        let src = model_label(indexes, &src_model_uuid);
+        let dst = model_label(indexes, &rel.target_model);
+
+        let cardinality = relation_cardinality(&format!("{:?}", rel.relation_type));
+        let rel_label = format!("{:?}", rel.relation_type);
+
+        mermaid.push_str(&format!(
+            "  {src} {cardinality} {dst} : \"{lbl}\"\n",
+            lbl = rel_label
+        ));
+    }
+
+    mermaid
+}
+
+fn dump_er_plantuml(data: &Structure, indexes: &UuidIndexes) -> String {
+    let mut plantuml = String::from("@startuml\n");
+
+    for model in &data.models {
+        plantuml.push_str(&format!("entity {} {{\n", model.object_name));
+        for field in &model.fields {
+            plantuml.push_str(&format!("  {}\n", field.name));
+        }
+        plantuml.push_str("}\n");
+    }
+
+    for rel in &data.relations {
+        let src_model_uuid = indexes.get_model_from_field(&rel.src_field);
+        let src = model_label(indexes, &src_model_uuid);
+        let dst = model_label(indexes, &rel.target_model);
+
+        let cardinality = relation_cardinality(&format!("{:?}", rel.relation_type));
+        let rel_label = format!("{:?}", rel.relation_type);
+
+        plantuml.push_str(&format!(
+            "{src} {cardinality} {dst} : {lbl}\n",
+            lbl = rel_label
+        ));
+    }
+
+    plantuml.push_str("@enduml\n");
+    plantuml
+}
+
 fn write<T>(to: &mut BufWriter<T>, data: &[u8])
 where
     T: Sized + Write,
@@ -219,28 +1150,93 @@ fn show_meta_data(meta_data: &MetaData) {
     write(&mut out, lines.join("\n").as_bytes());
 }
 
-fn rebuild(
-    data: Arc<Structure>,
-    indexes: UuidIndexes,
+/// Bidirectional BFS over a plain `(src, dst)` edge list: returns every uuid
+/// reachable from `root` (inclusive) within `max_depth` hops. Kept free of
+/// `Structure`/`UuidIndexes` so the traversal itself — depth limiting, both
+/// edge directions, cycle termination — is unit-testable on its own.
+fn bfs_reachable(edges: &[(String, String)], root: &str, max_depth: usize) -> HashSet<String> {
+    let mut visited: HashSet<String> = HashSet::new();
+    visited.insert(root.to_string());
+
+    let mut frontier = vec![root.to_string()];
+    let mut hops = 0;
+
+    while !frontier.is_empty() && hops < max_depth {
+        let mut next_frontier = Vec::new();
+
+        for uuid in &frontier {
+            for (src, dst) in edges {
+                let neighbor = if src == uuid {
+                    Some(dst.clone())
+                } else if dst == uuid {
+                    Some(src.clone())
+                } else {
+                    None
+                };
+
+                if let Some(neighbor) = neighbor
+                    && visited.insert(neighbor.clone())
+                {
+                    next_frontier.push(neighbor);
+                }
+            }
+        }
+
+        frontier = next_frontier;
+        hops += 1;
+    }
+
+    visited
+}
+
+/// Extracts the subgraph reachable from `model_uuid` by following
+/// `Structure::relations` in both directions (source model, via
+/// `indexes.get_model_from_field(&rel.src_field)`, and `rel.target_model`)
+/// up to `depth` hops, and returns it as a fresh `Structure` + `UuidIndexes`
+/// so `enumerate`/`dump_er_dot` only ever see the resulting neighborhood.
+fn reachable_subgraph(
+    data: &Arc<Structure>,
+    indexes: &UuidIndexes,
     model_uuid: String,
+    depth: Depth,
 ) -> (Arc<Structure>, UuidIndexes) {
-    let model = indexes.get_model(&model_uuid);
+    let max_depth = match depth {
+        Depth::Limited(n) => n,
+        Depth::All => usize::MAX,
+    };
 
-    let new_models = vec![model.clone()];
+    let edges: Vec<(String, String)> = data
+        .relations
+        .iter()
+        .map(|rel| {
+            (
+                indexes.get_model_from_field(&rel.src_field).to_string(),
+                rel.target_model.to_string(),
+            )
+        })
+        .collect();
+
+    let visited = bfs_reachable(&edges, &model_uuid, max_depth);
+
+    let new_models = data
+        .models
+        .iter()
+        .filter(|model| visited.contains(&model._meta_data.uuid))
+        .cloned()
+        .collect::<Vec<_>>();
 
-    // TODO: M2M
     let new_relations = data
         .relations
         .iter()
-        .filter(|rel| rel.target_model == model_uuid)
-        .map(|rel| rel.clone())
+        .filter(|rel| {
+            let src_model_uuid = indexes.get_model_from_field(&rel.src_field).to_string();
+            visited.contains(&src_model_uuid) && visited.contains(&rel.target_model)
+        })
+        .cloned()
         .collect::<Vec<_>>();
 
     let new_data = Structure {
-        models: new_models
-            .iter()
-            .map(|arc_model| Arc::clone(arc_model).as_ref().clone())
-            .collect(),
+        models: new_models,
         relations: new_relations,
     };
 
@@ -249,3 +1245,68 @@ fn rebuild(
 
     (shared, new_indexes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_levenshtein_exact_match_is_zero() {
+        assert_eq!(bounded_levenshtein("user", "user", 2), Some(0));
+    }
+
+    #[test]
+    fn bounded_levenshtein_within_cutoff() {
+        assert_eq!(bounded_levenshtein("user", "users", 2), Some(1));
+    }
+
+    #[test]
+    fn bounded_levenshtein_aborts_past_cutoff() {
+        assert_eq!(bounded_levenshtein("user", "completely_different", 2), None);
+    }
+
+    #[test]
+    fn match_distance_case_insensitive_prefix_is_zero() {
+        assert_eq!(match_distance("use", "UserProfile", 2), Some(0));
+    }
+
+    #[test]
+    fn match_distance_falls_back_to_levenshtein() {
+        assert_eq!(match_distance("usr", "user", 2), Some(1));
+    }
+
+    #[test]
+    fn bfs_reachable_limits_by_depth() {
+        let edges = vec![
+            ("a".to_string(), "b".to_string()),
+            ("b".to_string(), "c".to_string()),
+            ("c".to_string(), "d".to_string()),
+        ];
+
+        let one_hop = bfs_reachable(&edges, "a", 1);
+        assert_eq!(one_hop, HashSet::from(["a".to_string(), "b".to_string()]));
+
+        let two_hop = bfs_reachable(&edges, "a", 2);
+        assert_eq!(
+            two_hop,
+            HashSet::from(["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+    }
+
+    #[test]
+    fn bfs_reachable_follows_edges_in_both_directions() {
+        let edges = vec![("b".to_string(), "a".to_string())];
+        let reached = bfs_reachable(&edges, "a", 1);
+        assert!(reached.contains("b"));
+    }
+
+    #[test]
+    fn bfs_reachable_terminates_on_cycles() {
+        let edges = vec![
+            ("a".to_string(), "b".to_string()),
+            ("b".to_string(), "a".to_string()),
+        ];
+        let reached = bfs_reachable(&edges, "a", usize::MAX);
+        assert_eq!(reached, HashSet::from(["a".to_string(), "b".to_string()]));
+    }
+}